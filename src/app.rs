@@ -1,135 +1,243 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use egui_plot::PlotPoints;
 
-use crate::app::loader::CommitReport;
+use crate::app::persistence::RecentRepo;
+use crate::app::repo_browser::RemoteRepo;
+use crate::conventional_commit::CommitType;
+use crate::core::{self, Config, DataPoint, LoaderState};
 
-mod loader;
+mod persistence;
+mod repo_browser;
+mod vcs_url;
 
 pub struct App {
-    config: Config,
-    loader: loader::RepositoryLoader,
-    data: Vec<CommitReport>,
+    core: core::Core,
     show_settings: bool,
-    language_filter: HashSet<tokei::LanguageType>,
-    language_colors: HashMap<tokei::LanguageType, egui::Color32>,
     show_code: bool,
     show_files: bool,
     show_comments: bool,
     show_blanks: bool,
-}
-#[derive(Default, Clone, Debug)]
-pub struct Config {
-    pub depth: usize,
-    pub repo_url: String,
-    pub repo_branch: String,
+    recent_repos: Vec<RecentRepo>,
+    repo_browser: repo_browser::RepoBrowser,
+    browse_remote: bool,
+    browse_owner: String,
+    browse_token: String,
+    remote_repos: Vec<RemoteRepo>,
 }
 
-impl Config {
-    fn show(&mut self, ui: &mut egui::Ui) -> bool {
-        let mut clicked = false;
-        ui.vertical(|ui| {
-            egui::Grid::new("config")
-                .num_columns(2)
-                .striped(true)
-                .show(ui, |ui| {
-                    ui.label("Git repo path: ");
-                    ui.text_edit_singleline(&mut self.repo_url);
-                    ui.end_row();
-                    ui.label("Branch: ");
-                    ui.text_edit_singleline(&mut self.repo_branch);
-                    ui.end_row();
-                    ui.label("Depth:");
-                    ui.add(egui::widgets::DragValue::new(&mut self.depth));
-                });
-            if ui.button("process").clicked() {
-                clicked = true;
-            }
-        });
-        clicked
-    }
+fn show_config(config: &mut Config, ui: &mut egui::Ui, recent: &[RecentRepo]) -> bool {
+    let mut clicked = false;
+    ui.vertical(|ui| {
+        if !recent.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label("Recent:");
+                egui::ComboBox::from_id_salt("recent_repos")
+                    .selected_text("pick a recent repo")
+                    .show_ui(ui, |ui| {
+                        for entry in recent {
+                            let label = format!("{} ({})", entry.repo_url, entry.repo_branch);
+                            if ui.selectable_label(false, label).clicked() {
+                                config.repo_url = entry.repo_url.clone();
+                                config.repo_branch = entry.repo_branch.clone();
+                                config.depth = entry.depth;
+                            }
+                        }
+                    });
+            });
+        }
+        egui::Grid::new("config")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Git repo path: ");
+                ui.text_edit_singleline(&mut config.repo_url);
+                ui.end_row();
+                ui.label("Branch: ");
+                ui.text_edit_singleline(&mut config.repo_branch);
+                ui.end_row();
+                ui.label("Depth:");
+                ui.add(egui::widgets::DragValue::new(&mut config.depth));
+            });
+        if ui.button("process").clicked() {
+            clicked = true;
+        }
+    });
+    clicked
 }
+
 impl App {
-    pub fn new(config: Config, _: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(mut config: Config, _: &eframe::CreationContext<'_>) -> Self {
+        let mut show_code = true;
+        let mut show_files = false;
+        let mut show_comments = false;
+        let mut show_blanks = false;
+        let mut recent_repos = Vec::new();
+        let mut persisted_filter = None;
+        let mut persisted_colors = None;
+        if let Some(persisted) = persistence::load() {
+            persisted_filter = persisted.language_filter;
+            persisted_colors = Some(persisted.language_colors);
+            show_code = persisted.show_code;
+            show_files = persisted.show_files;
+            show_comments = persisted.show_comments;
+            show_blanks = persisted.show_blanks;
+            recent_repos = persisted.recent_repos;
+            if config.repo_url.is_empty() {
+                if let Some(last) = recent_repos.first() {
+                    config.repo_url = last.repo_url.clone();
+                    config.repo_branch = last.repo_branch.clone();
+                    config.depth = last.depth;
+                }
+            }
+        }
+        let mut core = core::Core::new(config);
+        if let Some(filter) = persisted_filter {
+            core.language_filter = core
+                .language_filter
+                .iter()
+                .cloned()
+                .filter(|l| filter.contains(&l.to_string()))
+                .collect();
+        }
+        if let Some(colors) = persisted_colors {
+            for (lang, color) in core.language_colors.iter_mut() {
+                if let Some(c) = colors.get(&lang.to_string()) {
+                    *color = *c;
+                }
+            }
+        }
         Self {
-            config,
-            loader: loader::RepositoryLoader::new(),
-            data: Vec::new(),
+            core,
             show_settings: false,
-            language_filter: Self::all_languages(),
-            language_colors: Self::generate_colors(),
-            show_blanks: false,
-            show_code: true,
-            show_comments: false,
-            show_files: false,
+            show_blanks,
+            show_code,
+            show_comments,
+            show_files,
+            recent_repos,
+            repo_browser: repo_browser::RepoBrowser::new(),
+            browse_remote: false,
+            browse_owner: String::new(),
+            browse_token: String::new(),
+            remote_repos: Vec::new(),
         }
     }
-    fn all_languages() -> HashSet<tokei::LanguageType> {
-        tokei::LanguageType::list()
-            .iter()
-            .cloned()
-            .collect::<HashSet<tokei::LanguageType>>()
-    }
-    fn generate_colors() -> HashMap<tokei::LanguageType, egui::Color32> {
-        Self::all_languages()
-            .into_iter()
-            .enumerate()
-            .map(|(i, l)| {
-                let golden_ratio = (5.0_f32.sqrt() - 1.0) / 2.0; // 0.61803398875
-                let h = i as f32 * golden_ratio;
-                let c: egui::Color32 = egui::ecolor::Hsva::new(h, 0.85, 0.5, 1.0).into();
-                (l, c)
-            })
-            .collect()
-    }
-    fn collect_data(
-        &self,
-        getter: impl Fn(&loader::CodeStats) -> usize,
-    ) -> Vec<(tokei::LanguageType, Vec<egui_plot::PlotPoint>)> {
-        tokei::LanguageType::list()
-            .iter()
-            .filter_map(|l| {
-                if self.language_filter.contains(l) {
-                    let loc_data: Vec<_> = self
-                        .data
-                        .iter()
-                        .filter_map(|report| {
-                            report.stats.iter().find(|s| s.language == *l).map(|s| {
-                                egui_plot::PlotPoint::new(
-                                    report.commit_date as f64,
-                                    getter(s) as f64,
-                                )
-                            })
-                        })
-                        .collect();
-                    Some((*l, loc_data))
-                } else {
-                    None
-                }
-            })
-            .collect()
+    /// Picks the marker shape used to outline a point by its commit type, so
+    /// `feat`/`fix`/`chore`/... commits are visually distinguishable on the
+    /// timeline.
+    fn marker_shape(commit_type: &CommitType) -> egui_plot::MarkerShape {
+        match commit_type {
+            CommitType::Feat => egui_plot::MarkerShape::Circle,
+            CommitType::Fix => egui_plot::MarkerShape::Diamond,
+            CommitType::Docs => egui_plot::MarkerShape::Square,
+            CommitType::Style => egui_plot::MarkerShape::Up,
+            CommitType::Refactor => egui_plot::MarkerShape::Cross,
+            CommitType::Perf => egui_plot::MarkerShape::Asterisk,
+            CommitType::Test => egui_plot::MarkerShape::Down,
+            CommitType::Build => egui_plot::MarkerShape::Plus,
+            CommitType::Ci => egui_plot::MarkerShape::Left,
+            CommitType::Chore => egui_plot::MarkerShape::Right,
+            CommitType::Revert => egui_plot::MarkerShape::X,
+            CommitType::Other(_) => egui_plot::MarkerShape::Diamond,
+            CommitType::Unconventional => egui_plot::MarkerShape::Circle,
+        }
     }
-    fn make_subplot<'d>(
+    fn make_subplot(
         &self,
-        shown_data: &'d [(tokei::LanguageType, Vec<egui_plot::PlotPoint>)],
-        plot_ui: &mut egui_plot::PlotUi<'d>,
+        shown_data: &[(tokei::LanguageType, Vec<DataPoint>)],
+        plot_ui: &mut egui_plot::PlotUi,
     ) {
         for (language, loc_data) in shown_data.iter() {
-            let color = *self.language_colors.get(language).unwrap();
-            let line = egui_plot::Line::new(language.to_string(), PlotPoints::Borrowed(loc_data))
+            let [r, g, b] = *self.core.language_colors.get(language).unwrap();
+            let color = egui::Color32::from_rgb(r, g, b);
+            let points: Vec<egui_plot::PlotPoint> = loc_data
+                .iter()
+                .map(|p| egui_plot::PlotPoint::new(p.date, p.value))
+                .collect();
+            let line = egui_plot::Line::new(language.to_string(), PlotPoints::Owned(points))
                 .style(egui_plot::LineStyle::dashed_dense())
                 .color(color)
                 .highlight(true);
-
             plot_ui.line(line);
-            let points =
-                egui_plot::Points::new(language.to_string(), PlotPoints::Borrowed(loc_data))
+
+            let mut by_shape: HashMap<egui_plot::MarkerShape, Vec<egui_plot::PlotPoint>> =
+                HashMap::new();
+            for p in loc_data.iter() {
+                by_shape
+                    .entry(Self::marker_shape(&p.commit_type))
+                    .or_default()
+                    .push(egui_plot::PlotPoint::new(p.date, p.value));
+            }
+            for (shape, points) in by_shape {
+                let points = egui_plot::Points::new(language.to_string(), PlotPoints::Owned(points))
                     .radius(4.0)
+                    .shape(shape)
                     .color(color)
                     .allow_hover(true);
-            plot_ui.points(points);
+                plot_ui.points(points);
+            }
+        }
+    }
+    /// Finds the commit hash of the point in `shown_data` nearest to `coord`
+    /// along the x (date) axis, so a click anywhere near a commit's marker
+    /// resolves to that commit.
+    fn nearest_commit_hash(
+        shown_data: &[(tokei::LanguageType, Vec<DataPoint>)],
+        coord: egui_plot::PlotPoint,
+    ) -> Option<String> {
+        shown_data
+            .iter()
+            .flat_map(|(_, points)| points.iter())
+            .min_by(|a, b| {
+                (a.date - coord.x)
+                    .abs()
+                    .partial_cmp(&(b.date - coord.x).abs())
+                    .unwrap()
+            })
+            .map(|p| p.commit_hash.clone())
+    }
+    fn open_commit(&self, hash: &str) {
+        match vcs_url::commit_url(&self.core.config.repo_url, hash) {
+            Some(url) => {
+                if let Err(e) = open::that(&url) {
+                    log::error!("Failed to open {url}: {e}");
+                }
+            }
+            None => log::warn!(
+                "Could not derive a commit URL for remote {}",
+                self.core.config.repo_url
+            ),
         }
     }
+    /// Net code lines added/removed per commit type, newest-to-oldest deltas
+    /// attributed to the newer commit of each adjacent pair, sorted by
+    /// magnitude so the biggest contributors to growth surface first.
+    fn commit_type_breakdown(&self) -> Vec<(CommitType, i64)> {
+        let mut net: HashMap<CommitType, i64> = HashMap::new();
+        for window in self.core.data.windows(2) {
+            let newer_total: i64 = window[0].stats.iter().map(|s| s.code as i64).sum();
+            let older_total: i64 = window[1].stats.iter().map(|s| s.code as i64).sum();
+            *net.entry(window[0].commit_type.clone()).or_insert(0) += newer_total - older_total;
+        }
+        let mut result: Vec<_> = net.into_iter().collect();
+        result.sort_by(|a, b| b.1.abs().cmp(&a.1.abs()));
+        result
+    }
+    fn show_commit_type_panel(&self, ui: &mut egui::Ui) {
+        ui.collapsing("Changes by commit type", |ui| {
+            egui::Grid::new("commit_type_grid")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (commit_type, net_lines) in self.commit_type_breakdown() {
+                        ui.label(commit_type.to_string());
+                        let sign = if net_lines >= 0 { "+" } else { "" };
+                        ui.label(format!("{sign}{net_lines}"));
+                        ui.end_row();
+                    }
+                });
+        });
+    }
     fn make_plot(&self, _: &egui::Context, ui: &mut egui::Ui) {
         let x_axes = egui_plot::AxisHints::new_x().label("Date").formatter(
             |mark: egui_plot::GridMark, _| {
@@ -138,8 +246,9 @@ impl App {
                 date.format("%Y-%m-%d").to_string()
             },
         );
-        let code_data = self.collect_data(|s| s.code);
-        let files_data = self.collect_data(|s| s.files);
+        let code_data = self.core.collect_data(|s| s.code);
+        let files_data = self.core.collect_data(|s| s.files);
+        let mut clicked_coord = None;
         egui_plot::Plot::new("plot")
             .custom_x_axes(vec![x_axes])
             .show(ui, |plot_ui| {
@@ -149,21 +258,69 @@ impl App {
                 if self.show_files {
                     self.make_subplot(&files_data, plot_ui);
                 }
-
+                if plot_ui.response().clicked() {
+                    clicked_coord = plot_ui.pointer_coordinate();
+                }
+            });
+        if let Some(coord) = clicked_coord {
+            let hash = Self::nearest_commit_hash(&code_data, coord)
+                .or_else(|| Self::nearest_commit_hash(&files_data, coord));
+            if let Some(hash) = hash {
+                self.open_commit(&hash);
+            }
+        }
+    }
+    fn show_browse_remote_panel(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.browse_remote, "Browse remote");
+        if !self.browse_remote {
+            return;
+        }
+        if let Some(result) = self.repo_browser.try_recv() {
+            match result {
+                Ok(repos) => self.remote_repos = repos,
+                Err(e) => log::error!("Failed to list repositories: {e}"),
+            }
+        }
+        egui::Grid::new("browse_remote_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("User/org:");
+                ui.text_edit_singleline(&mut self.browse_owner);
+                ui.end_row();
+                ui.label("Token:");
+                ui.add(egui::TextEdit::singleline(&mut self.browse_token).password(true));
+                ui.end_row();
             });
+        if ui.button("Refresh").clicked() && !self.browse_owner.is_empty() {
+            self.repo_browser
+                .get_repos(self.browse_owner.clone(), self.browse_token.clone());
+        }
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for repo in &self.remote_repos {
+                    if ui.selectable_label(false, &repo.full_name).clicked() {
+                        self.core.config.repo_url = repo.clone_url.clone();
+                        self.core.config.repo_branch = repo.default_branch.clone();
+                        if !self.browse_token.is_empty() {
+                            // Let `clone_remote`'s GIT_USERNAME/GIT_PASSWORD fallback
+                            // authenticate the clone of this (possibly private) repo.
+                            std::env::set_var("GIT_USERNAME", &self.browse_owner);
+                            std::env::set_var("GIT_PASSWORD", &self.browse_token);
+                        }
+                    }
+                }
+            });
+        ui.separator();
     }
     fn show_config_panel(&mut self, ui: &mut egui::Ui) {
         ui.vertical_centered(|ui| {
             ui.heading("Settings");
         });
         ui.separator();
-        let mut used_languages: HashSet<tokei::LanguageType> = HashSet::default();
-        for d in &self.data {
-            for s in &d.stats {
-                used_languages.insert(s.language);
-            }
-        }
-        // self.language_filter.clear();
+        self.show_browse_remote_panel(ui);
+        let used_languages = self.core.used_languages();
+        // self.core.language_filter.clear();
         egui::Grid::new("settings_grid")
             .num_columns(3)
             .striped(true)
@@ -183,26 +340,31 @@ impl App {
                 ui.checkbox(&mut self.show_files, ());
                 ui.end_row();
                 ui.label(egui::RichText::from("Languages").underline());
-                let mut any_selected = !self.language_filter.is_empty();
+                let mut any_selected = !self.core.language_filter.is_empty();
                 let any_selected_copy = any_selected;
                 ui.checkbox(&mut any_selected, ());
                 if !any_selected && any_selected_copy {
-                    self.language_filter.clear();
+                    self.core.language_filter.clear();
                 } else if any_selected && !any_selected_copy {
-                    self.language_filter = Self::all_languages();
+                    self.core.language_filter = core::Core::all_languages();
                 }
                 ui.end_row();
                 for lang in tokei::LanguageType::list() {
                     if used_languages.contains(lang) {
                         ui.label(lang.to_string());
-                        let mut selected = self.language_filter.contains(lang);
+                        let mut selected = self.core.language_filter.contains(lang);
                         ui.checkbox(&mut selected, ());
                         if selected {
-                            self.language_filter.insert(*lang);
+                            self.core.language_filter.insert(*lang);
                         } else {
-                            self.language_filter.remove(lang);
+                            self.core.language_filter.remove(lang);
                         }
-                        ui.color_edit_button_srgba(self.language_colors.get_mut(lang).unwrap());
+                        let [r, g, b] = *self.core.language_colors.get(lang).unwrap();
+                        let mut color = egui::Color32::from_rgb(r, g, b);
+                        ui.color_edit_button_srgba(&mut color);
+                        self.core
+                            .language_colors
+                            .insert(*lang, [color.r(), color.g(), color.b()]);
                         ui.end_row();
                     }
                 }
@@ -211,20 +373,85 @@ impl App {
     fn show_plot_panel(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
         // The central panel the region left after adding TopPanel's and SidePanel's
         ui.heading("Oshmornegar");
-        if self.config.show(ui) {
-            self.loader.update_config(self.config.clone());
+        if show_config(&mut self.core.config, ui, &self.recent_repos) {
             log::debug!("Start processing");
-            self.data.clear();
-        }
-        if let Some(Ok(loader::LoaderData::CommitReport(r))) = self.loader.try_recv() {
-            self.data.push(r);
+            self.core.start();
+            persistence::push_recent_repo(
+                &mut self.recent_repos,
+                persistence::RecentRepo {
+                    repo_url: self.core.config.repo_url.clone(),
+                    repo_branch: self.core.config.repo_branch.clone(),
+                    depth: self.core.config.depth,
+                },
+            );
         }
+        self.core.poll();
+        self.show_loader_status(ui);
         ui.ctx().request_repaint();
         self.make_plot(ctx, ui);
+        self.show_commit_type_panel(ui);
+    }
+    fn show_loader_status(&self, ui: &mut egui::Ui) {
+        match &self.core.loader_state {
+            LoaderState::Idle => {}
+            LoaderState::Cloning {
+                received_objects,
+                total_objects,
+                received_bytes,
+            } => {
+                let progress = if *total_objects > 0 {
+                    *received_objects as f32 / *total_objects as f32
+                } else {
+                    0.0
+                };
+                ui.add(egui::ProgressBar::new(progress).show_percentage());
+                ui.label(format!(
+                    "Cloning: {received_objects}/{total_objects} objects ({} KB)",
+                    received_bytes / 1024
+                ));
+            }
+            LoaderState::Walking { current, total } => {
+                let progress = if *total > 0 {
+                    *current as f32 / *total as f32
+                } else {
+                    0.0
+                };
+                ui.add(egui::ProgressBar::new(progress).show_percentage());
+                ui.label(format!("Analyzing commit {current} of {total}"));
+            }
+            LoaderState::Done => {
+                ui.label("Done");
+            }
+            LoaderState::Error(e) => {
+                ui.colored_label(egui::Color32::RED, format!("Error: {e}"));
+            }
+        }
     }
 }
 impl eframe::App for App {
-    fn save(&mut self, _storage: &mut dyn eframe::Storage) {}
+    fn save(&mut self, _storage: &mut dyn eframe::Storage) {
+        let state = persistence::PersistedState {
+            recent_repos: self.recent_repos.clone(),
+            language_filter: Some(
+                self.core
+                    .language_filter
+                    .iter()
+                    .map(|l| l.to_string())
+                    .collect(),
+            ),
+            language_colors: self
+                .core
+                .language_colors
+                .iter()
+                .map(|(l, c)| (l.to_string(), *c))
+                .collect(),
+            show_code: self.show_code,
+            show_files: self.show_files,
+            show_comments: self.show_comments,
+            show_blanks: self.show_blanks,
+        };
+        persistence::save(&state);
+    }
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             // The top panel is often a good place for a menu bar:
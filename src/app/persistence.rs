@@ -0,0 +1,72 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+const MAX_RECENT_REPOS: usize = 10;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RecentRepo {
+    pub repo_url: String,
+    pub repo_branch: String,
+    pub depth: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PersistedState {
+    pub recent_repos: Vec<RecentRepo>,
+    /// `None` means no filter was ever saved (fresh install); `Some(set)` is
+    /// the user's saved selection, which may legitimately be empty if they
+    /// deselected every language before closing the app.
+    pub language_filter: Option<HashSet<String>>,
+    pub language_colors: HashMap<String, [u8; 3]>,
+    pub show_code: bool,
+    pub show_files: bool,
+    pub show_comments: bool,
+    pub show_blanks: bool,
+}
+
+fn config_path() -> Option<PathBuf> {
+    let dirs = ProjectDirs::from("dev", "mayyxeng", "vgloc")?;
+    Some(dirs.config_dir().join("config.json"))
+}
+
+pub fn load() -> Option<PersistedState> {
+    let path = config_path()?;
+    let data = std::fs::read_to_string(&path)
+        .map_err(|e| log::debug!("No persisted config at {}: {e}", path.to_string_lossy()))
+        .ok()?;
+    serde_json::from_str(&data)
+        .map_err(|e| log::warn!("Failed to parse persisted config: {e}"))
+        .ok()
+}
+
+pub fn save(state: &PersistedState) {
+    let Some(path) = config_path() else {
+        log::warn!("Could not determine config directory, not persisting state");
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            log::error!("Failed to create config dir {}: {e}", parent.to_string_lossy());
+            return;
+        }
+    }
+    match serde_json::to_string_pretty(state) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                log::error!("Failed to write config to {}: {e}", path.to_string_lossy());
+            }
+        }
+        Err(e) => log::error!("Failed to serialize persisted state: {e}"),
+    }
+}
+
+/// Inserts `entry` at the front of `recent`, removing any existing entry for
+/// the same repo/branch and capping the list at `MAX_RECENT_REPOS`.
+pub fn push_recent_repo(recent: &mut Vec<RecentRepo>, entry: RecentRepo) {
+    recent.retain(|r| r.repo_url != entry.repo_url || r.repo_branch != entry.repo_branch);
+    recent.insert(0, entry);
+    recent.truncate(MAX_RECENT_REPOS);
+}
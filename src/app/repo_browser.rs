@@ -0,0 +1,152 @@
+use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError};
+use std::thread::JoinHandle;
+
+use octocrab::Octocrab;
+
+#[derive(Debug, Clone)]
+pub struct RemoteRepo {
+    pub full_name: String,
+    pub clone_url: String,
+    pub default_branch: String,
+}
+
+pub enum BrowserCommand {
+    GetRepos { owner: String, token: String },
+    Die,
+}
+
+pub enum BrowserError {
+    Octocrab(octocrab::Error),
+}
+impl From<octocrab::Error> for BrowserError {
+    fn from(value: octocrab::Error) -> Self {
+        Self::Octocrab(value)
+    }
+}
+impl std::fmt::Debug for BrowserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Octocrab(e) => write!(f, "Repo browser error: {e:?}"),
+        }
+    }
+}
+impl std::fmt::Display for BrowserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Octocrab(e) => write!(f, "Repo browser error: {e}"),
+        }
+    }
+}
+impl std::error::Error for BrowserError {}
+
+/// Lists repositories for a GitHub account/org on a worker thread, analogous
+/// to `RepositoryLoader`, so listing never blocks the UI thread.
+pub struct RepoBrowser {
+    command_tx: SyncSender<BrowserCommand>,
+    data_rx: Receiver<Result<Vec<RemoteRepo>, BrowserError>>,
+    worker: JoinHandle<()>,
+}
+
+impl RepoBrowser {
+    pub fn new() -> Self {
+        log::debug!("Creating a repo browser");
+        let (command_tx, command_rx) = mpsc::sync_channel::<BrowserCommand>(1);
+        let (data_tx, data_rx) = mpsc::sync_channel::<Result<Vec<RemoteRepo>, BrowserError>>(8);
+        let worker = std::thread::spawn(move || {
+            browser_main(command_rx, data_tx);
+        });
+        Self {
+            command_tx,
+            data_rx,
+            worker,
+        }
+    }
+
+    pub fn get_repos(&self, owner: String, token: String) {
+        self.command_tx
+            .send(BrowserCommand::GetRepos { owner, token })
+            .unwrap();
+        log::debug!("Requested repository listing");
+    }
+
+    pub fn try_recv(&self) -> Option<Result<Vec<RemoteRepo>, BrowserError>> {
+        match self.data_rx.try_recv() {
+            Ok(value) => Some(value),
+            Err(TryRecvError::Disconnected) => panic!("thread is dead"),
+            _ => None,
+        }
+    }
+}
+
+impl Drop for RepoBrowser {
+    fn drop(&mut self) {
+        let _ = self.command_tx.send(BrowserCommand::Die);
+    }
+}
+
+fn browser_main(
+    command_rx: Receiver<BrowserCommand>,
+    data_tx: SyncSender<Result<Vec<RemoteRepo>, BrowserError>>,
+) {
+    log::debug!("Repo browser thread started");
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to start repo browser runtime");
+    loop {
+        match command_rx.recv() {
+            Ok(BrowserCommand::Die) | Err(_) => break,
+            Ok(BrowserCommand::GetRepos { owner, token }) => {
+                let result = runtime.block_on(list_repos(owner, token));
+                if data_tx.send(result).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    log::debug!("Repo browser thread ended");
+}
+
+/// Lists an account's repos. `GET /users/{username}/repos` (what
+/// `octocrab.users(...).repos()` hits) only ever returns public repos, no
+/// matter how the request is authenticated, so browsing your own account by
+/// username would silently hide your private repos. If `owner` matches the
+/// token's own account, list via `current().list_repos_for_authenticated_user()`
+/// instead, which does include private repos; otherwise fall back to the org
+/// listing (private-capable for orgs the token belongs to) and finally the
+/// public user listing.
+async fn list_repos(owner: String, token: String) -> Result<Vec<RemoteRepo>, BrowserError> {
+    let octocrab = Octocrab::builder().personal_token(token).build()?;
+
+    let is_own_account = octocrab
+        .current()
+        .user()
+        .await
+        .map(|u| u.login.eq_ignore_ascii_case(&owner))
+        .unwrap_or(false);
+
+    let page = if is_own_account {
+        octocrab
+            .current()
+            .list_repos_for_authenticated_user()
+            .send()
+            .await?
+    } else {
+        match octocrab.orgs(&owner).list_repos().send().await {
+            Ok(page) => page,
+            Err(_) => octocrab.users(&owner).repos().send().await?,
+        }
+    };
+    Ok(page
+        .items
+        .into_iter()
+        .map(|repo| RemoteRepo {
+            full_name: repo.full_name.unwrap_or_else(|| repo.name.clone()),
+            clone_url: repo
+                .clone_url
+                .map(|u| u.to_string())
+                .unwrap_or_else(|| repo.html_url.map(|u| u.to_string()).unwrap_or_default()),
+            default_branch: repo.default_branch.unwrap_or_else(|| "main".to_owned()),
+        })
+        .collect())
+}
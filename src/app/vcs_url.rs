@@ -0,0 +1,23 @@
+/// Builds a browsable commit URL for a GitHub/GitLab-style remote.
+///
+/// Understands both the SSH shorthand (`git@host:owner/repo.git`) and the
+/// HTTPS form (`https://host/owner/repo.git`), with or without the trailing
+/// `.git`. Returns `None` if `repo_url` doesn't match either shape.
+pub fn commit_url(repo_url: &str, commit_hash: &str) -> Option<String> {
+    let (host, path) = if let Some(rest) = repo_url.strip_prefix("git@") {
+        rest.split_once(':')?
+    } else {
+        let rest = repo_url
+            .strip_prefix("https://")
+            .or_else(|| repo_url.strip_prefix("http://"))?;
+        rest.split_once('/')?
+    };
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+    // GitLab serves commit pages under a `/-/` segment; GitHub does not.
+    let sep = if host.contains("gitlab") { "/-/commit" } else { "/commit" };
+    Some(format!("https://{host}/{owner}/{repo}{sep}/{commit_hash}"))
+}
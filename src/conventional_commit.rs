@@ -0,0 +1,152 @@
+/// The `type` portion of a Conventional Commits header.
+///
+/// Known types get their own variant so callers can match on them without
+/// string comparisons; anything else still satisfies the grammar and is kept
+/// verbatim in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CommitType {
+    Feat,
+    Fix,
+    Docs,
+    Style,
+    Refactor,
+    Perf,
+    Test,
+    Build,
+    Ci,
+    Chore,
+    Revert,
+    Other(String),
+    Unconventional,
+}
+
+impl std::fmt::Display for CommitType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Feat => write!(f, "feat"),
+            Self::Fix => write!(f, "fix"),
+            Self::Docs => write!(f, "docs"),
+            Self::Style => write!(f, "style"),
+            Self::Refactor => write!(f, "refactor"),
+            Self::Perf => write!(f, "perf"),
+            Self::Test => write!(f, "test"),
+            Self::Build => write!(f, "build"),
+            Self::Ci => write!(f, "ci"),
+            Self::Chore => write!(f, "chore"),
+            Self::Revert => write!(f, "revert"),
+            Self::Other(t) => write!(f, "{t}"),
+            Self::Unconventional => write!(f, "unconventional"),
+        }
+    }
+}
+
+impl From<&str> for CommitType {
+    fn from(value: &str) -> Self {
+        match value {
+            "feat" => Self::Feat,
+            "fix" => Self::Fix,
+            "docs" => Self::Docs,
+            "style" => Self::Style,
+            "refactor" => Self::Refactor,
+            "perf" => Self::Perf,
+            "test" => Self::Test,
+            "build" => Self::Build,
+            "ci" => Self::Ci,
+            "chore" => Self::Chore,
+            "revert" => Self::Revert,
+            other => Self::Other(other.to_owned()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedCommit {
+    pub commit_type: CommitType,
+    pub breaking: bool,
+}
+
+/// Parses a commit message's header as `type(scope)!: description`.
+///
+/// `scope` is optional, a trailing `!` before the colon or a `BREAKING
+/// CHANGE:` footer anywhere in the message marks a breaking change, and
+/// anything that doesn't match this shape is `CommitType::Unconventional`.
+pub fn parse(message: &str) -> ParsedCommit {
+    let breaking_footer = message.contains("BREAKING CHANGE:");
+    let header = message.lines().next().unwrap_or("");
+    let Some(colon_idx) = header.find(':') else {
+        return ParsedCommit {
+            commit_type: CommitType::Unconventional,
+            breaking: breaking_footer,
+        };
+    };
+    let (prefix, description) = header.split_at(colon_idx);
+    if !description.starts_with(": ") && description != ":" {
+        return ParsedCommit {
+            commit_type: CommitType::Unconventional,
+            breaking: breaking_footer,
+        };
+    }
+    let (prefix, bang) = match prefix.strip_suffix('!') {
+        Some(p) => (p, true),
+        None => (prefix, false),
+    };
+    let type_str = match prefix.find('(') {
+        Some(paren_idx) => {
+            if !prefix.ends_with(')') {
+                return ParsedCommit {
+                    commit_type: CommitType::Unconventional,
+                    breaking: breaking_footer,
+                };
+            }
+            &prefix[..paren_idx]
+        }
+        None => prefix,
+    };
+    let is_valid_type = !type_str.is_empty()
+        && type_str
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !is_valid_type {
+        return ParsedCommit {
+            commit_type: CommitType::Unconventional,
+            breaking: breaking_footer,
+        };
+    }
+    ParsedCommit {
+        commit_type: CommitType::from(type_str),
+        breaking: bang || breaking_footer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_scope_and_type() {
+        let parsed = parse("feat(loader): walk trees instead of checking out");
+        assert_eq!(parsed.commit_type, CommitType::Feat);
+        assert!(!parsed.breaking);
+    }
+
+    #[test]
+    fn bang_before_colon_marks_breaking() {
+        let parsed = parse("refactor(core)!: drop the old Config shape");
+        assert_eq!(parsed.commit_type, CommitType::Refactor);
+        assert!(parsed.breaking);
+    }
+
+    #[test]
+    fn breaking_change_footer_marks_breaking() {
+        let parsed = parse("fix: drop legacy field\n\nBREAKING CHANGE: removes the old field");
+        assert_eq!(parsed.commit_type, CommitType::Fix);
+        assert!(parsed.breaking);
+    }
+
+    #[test]
+    fn header_without_colon_is_unconventional() {
+        let parsed = parse("quick fix for the build");
+        assert_eq!(parsed.commit_type, CommitType::Unconventional);
+        assert!(!parsed.breaking);
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::conventional_commit::CommitType;
+use crate::loader::{self, CommitReport};
+
+#[derive(Default, Clone, Debug)]
+pub struct Config {
+    pub depth: usize,
+    pub repo_url: String,
+    pub repo_branch: String,
+}
+
+/// A single plotted sample: a language's code/file count at one commit, kept
+/// frontend-agnostic so both the egui plot and the TUI chart draw off the
+/// same data.
+#[derive(Debug, Clone)]
+pub struct DataPoint {
+    pub date: f64,
+    pub value: f64,
+    pub commit_hash: String,
+    pub commit_type: CommitType,
+}
+
+/// Tracks the loader's progress purely from the messages it sends.
+#[derive(Debug, Clone, Default)]
+pub enum LoaderState {
+    #[default]
+    Idle,
+    Cloning {
+        received_objects: usize,
+        total_objects: usize,
+        received_bytes: usize,
+    },
+    Walking {
+        current: usize,
+        total: usize,
+    },
+    Done,
+    Error(String),
+}
+
+/// The rendering-agnostic core of the app: config, the loader pipeline, and
+/// language filtering/coloring. Both the egui `App` and the terminal
+/// frontend wrap a `Core` and render its `CommitReport`s their own way.
+pub struct Core {
+    pub config: Config,
+    pub data: Vec<CommitReport>,
+    pub language_filter: HashSet<tokei::LanguageType>,
+    pub language_colors: HashMap<tokei::LanguageType, [u8; 3]>,
+    pub loader_state: LoaderState,
+    loader: loader::RepositoryLoader,
+}
+
+impl Core {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            data: Vec::new(),
+            language_filter: Self::all_languages(),
+            language_colors: Self::generate_colors(),
+            loader_state: LoaderState::Idle,
+            loader: loader::RepositoryLoader::new(),
+        }
+    }
+
+    pub fn all_languages() -> HashSet<tokei::LanguageType> {
+        tokei::LanguageType::list()
+            .iter()
+            .cloned()
+            .collect::<HashSet<tokei::LanguageType>>()
+    }
+
+    pub fn generate_colors() -> HashMap<tokei::LanguageType, [u8; 3]> {
+        Self::all_languages()
+            .into_iter()
+            .enumerate()
+            .map(|(i, l)| {
+                let golden_ratio = (5.0_f32.sqrt() - 1.0) / 2.0; // 0.61803398875
+                let h = i as f32 * golden_ratio;
+                let c: egui::Color32 = egui::ecolor::Hsva::new(h, 0.85, 0.5, 1.0).into();
+                (l, [c.r(), c.g(), c.b()])
+            })
+            .collect()
+    }
+
+    /// Starts (or restarts) a walk over `self.config`, clearing previously
+    /// collected data.
+    pub fn start(&mut self) {
+        self.loader.update_config(self.config.clone());
+        self.data.clear();
+        self.loader_state = LoaderState::Cloning {
+            received_objects: 0,
+            total_objects: 0,
+            received_bytes: 0,
+        };
+    }
+
+    /// Drains all pending loader messages, folding them into `data` and
+    /// `loader_state`. Cheap to call every frame/tick.
+    pub fn poll(&mut self) {
+        while let Some(message) = self.loader.try_recv() {
+            match message {
+                Ok(loader::LoaderData::CommitReport(r)) => self.data.push(r),
+                Ok(loader::LoaderData::FetchProgress {
+                    received_objects,
+                    total_objects,
+                    received_bytes,
+                }) => {
+                    self.loader_state = LoaderState::Cloning {
+                        received_objects,
+                        total_objects,
+                        received_bytes,
+                    };
+                }
+                Ok(loader::LoaderData::WalkProgress { current, total }) => {
+                    self.loader_state = LoaderState::Walking { current, total };
+                }
+                Ok(loader::LoaderData::Done) => self.loader_state = LoaderState::Done,
+                Err(e) => self.loader_state = LoaderState::Error(format!("{e}")),
+            }
+        }
+    }
+
+    /// Collects per-language data points for the currently filtered
+    /// languages, using `getter` to pick which metric (code, files, ...) to
+    /// plot.
+    pub fn collect_data(
+        &self,
+        getter: impl Fn(&loader::CodeStats) -> usize,
+    ) -> Vec<(tokei::LanguageType, Vec<DataPoint>)> {
+        tokei::LanguageType::list()
+            .iter()
+            .filter_map(|l| {
+                if self.language_filter.contains(l) {
+                    let points: Vec<_> = self
+                        .data
+                        .iter()
+                        .filter_map(|report| {
+                            report.stats.iter().find(|s| s.language == *l).map(|s| DataPoint {
+                                date: report.commit_date as f64,
+                                value: getter(s) as f64,
+                                commit_hash: report.commit_hash.clone(),
+                                commit_type: report.commit_type.clone(),
+                            })
+                        })
+                        .collect();
+                    Some((*l, points))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn used_languages(&self) -> HashSet<tokei::LanguageType> {
+        let mut used = HashSet::default();
+        for d in &self.data {
+            for s in &d.stats {
+                used.insert(s.language);
+            }
+        }
+        used
+    }
+}
@@ -1,14 +1,30 @@
-use crate::app::Config;
+use crate::conventional_commit::{self, CommitType};
+use crate::core::Config;
 use git2::build::RepoBuilder;
-use git2::{Cred, FetchOptions, RemoteCallbacks, Repository};
+use git2::{Cred, FetchOptions, ObjectType, Oid, RemoteCallbacks, Repository, TreeWalkMode, TreeWalkResult};
+use lru::LruCache;
+use std::collections::HashMap;
 use std::env;
+use std::num::NonZeroUsize;
 use tempfile::TempDir;
 
 use std::path::Path;
 use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError};
 use std::thread::JoinHandle;
 
-use tokei::{LanguageType, Languages};
+use tokei::LanguageType;
+
+/// Blob-OID cache size: unrelated commits in a deep walk still tend to share
+/// most of their files, so a fairly large cache keeps the hit rate high.
+const BLOB_CACHE_SIZE: usize = 20_000;
+
+#[derive(Debug, Clone)]
+struct BlobStats {
+    language: LanguageType,
+    code: usize,
+    comments: usize,
+    blanks: usize,
+}
 
 #[derive(Debug)]
 pub struct CodeStats {
@@ -22,6 +38,8 @@ pub struct CodeStats {
 pub struct CommitReport {
     pub commit_date: i64,
     pub commit_hash: String,
+    pub commit_type: CommitType,
+    pub breaking: bool,
     pub stats: Vec<CodeStats>,
 }
 pub enum LoaderCommand {
@@ -30,7 +48,16 @@ pub enum LoaderCommand {
 }
 pub enum LoaderData {
     CommitReport(CommitReport),
-    FetchProgress,
+    FetchProgress {
+        received_objects: usize,
+        total_objects: usize,
+        received_bytes: usize,
+    },
+    WalkProgress {
+        current: usize,
+        total: usize,
+    },
+    Done,
 }
 
 pub struct RepositoryLoader {
@@ -77,14 +104,17 @@ impl RepositoryLoader {
     }
 }
 
-fn initialize(config: &Config) -> Result<RepositoryHandle, LoaderError> {
+fn initialize(
+    config: &Config,
+    data_tx: &SyncSender<Result<LoaderData, LoaderError>>,
+) -> Result<RepositoryHandle, LoaderError> {
     let temp_dir = tempfile::tempdir_in(".")
         .map_err(|_| LoaderError::Other("Failed to create temp dir".to_owned()))?;
     let repo_path = temp_dir.path();
     let repository = if is_local_repo(&config.repo_url) {
         copy_local_repo(Path::new(&config.repo_url), repo_path)
     } else {
-        clone_remote(&config.repo_url, repo_path)
+        clone_remote(&config.repo_url, repo_path, data_tx)
     }?;
     let handle = RepositoryHandle {
         repository,
@@ -128,48 +158,52 @@ fn loader_loop(
     let RepositoryHandle {
         temp_dir,
         repository,
-    } = initialize(&config)?;
+    } = initialize(&config, data_tx)?;
     let repo_path = temp_dir.path();
     log::debug!("Repository cloned to {}", repo_path.to_string_lossy());
     let mut revwalk = repository.revwalk().expect("Failed to get revwalk");
+    // `commit_type_breakdown` in app.rs assumes adjacent entries in `data`
+    // are adjacent in commit time, so the walk must be time-ordered rather
+    // than libgit2's unspecified default order.
+    revwalk.set_sorting(git2::Sort::TIME).unwrap();
     let obj = repository
         .revparse_single(&format!("refs/remotes/origin/{}", config.repo_branch))
         .or_else(|_| repository.revparse_single(&format!("refs/heads/{}", config.repo_branch)))
         .map_err(|e| LoaderError::Other(format!("{e}")))?;
     revwalk.push(obj.id()).unwrap();
 
-    for maybe_commit_id in revwalk.take(config.depth) {
+    let tokei_config = tokei::Config::default();
+    let mut blob_cache: LruCache<(Oid, LanguageType), Option<BlobStats>> =
+        LruCache::new(NonZeroUsize::new(BLOB_CACHE_SIZE).unwrap());
+
+    for (i, maybe_commit_id) in revwalk.take(config.depth).enumerate() {
         let commit_id = maybe_commit_id.map_err(|e| LoaderError::Other(format!("{e}")))?;
         let commit = repository.find_commit(commit_id).unwrap();
         let tree = commit.tree().unwrap();
 
-        repository.checkout_tree(tree.as_object(), None).unwrap();
-        repository.set_head_detached(commit.id()).unwrap();
-
-        let config = tokei::Config::default();
-        let mut stats = Languages::new();
-        stats.get_statistics(&[repo_path.to_path_buf()], &[], &config);
+        let stats = tree_stats(&repository, &tree, &mut blob_cache, &tokei_config);
 
         log::debug!("commit date: {}", commit.time().seconds());
         log::debug!("commit hash: {}", commit.id());
+        let parsed = conventional_commit::parse(commit.message().unwrap_or_default());
         let report = CommitReport {
             commit_date: commit.time().seconds(),
             commit_hash: commit.id().to_string(),
-            stats: stats
-                .iter()
-                .map(|(l, d)| CodeStats {
-                    language: *l,
-                    files: d.reports.len(),
-                    code: d.code,
-                    blanks: d.blanks,
-                    comments: d.comments,
-                })
-                .collect(),
+            commit_type: parsed.commit_type,
+            breaking: parsed.breaking,
+            stats,
         };
         log::debug!("Report: {report:?}");
         data_tx.send(Ok(LoaderData::CommitReport(report))).unwrap();
+        data_tx
+            .send(Ok(LoaderData::WalkProgress {
+                current: i + 1,
+                total: config.depth,
+            }))
+            .unwrap();
     }
     log::debug!("Finished processing repository");
+    data_tx.send(Ok(LoaderData::Done)).unwrap();
     Ok(LoaderQuiteCause::Finished)
 }
 
@@ -200,11 +234,95 @@ impl std::fmt::Display for LoaderError {
     }
 }
 
+/// Looks up (and caches) the language/code-stats for a single blob, so a file
+/// whose content is unchanged across commits (same OID) is parsed exactly
+/// once no matter how many commits in the walk contain it.
+///
+/// Blob OIDs are content-addressed, not path-addressed, so two files with
+/// identical content but different extensions (most commonly empty files
+/// like `__init__.py`, `.gitkeep`, or stub headers) can share an OID. The
+/// cache is therefore keyed on `(Oid, LanguageType)`, not `Oid` alone, so
+/// each language's stats for that content are cached independently.
+fn stats_for_blob(
+    repository: &Repository,
+    oid: Oid,
+    path: &Path,
+    cache: &mut LruCache<(Oid, LanguageType), Option<BlobStats>>,
+    tokei_config: &tokei::Config,
+) -> Option<BlobStats> {
+    let language = LanguageType::from_path(path, tokei_config)?;
+    let key = (oid, language);
+    if let Some(cached) = cache.get(&key) {
+        return cached.clone();
+    }
+    let stats = (|| {
+        let blob = repository.find_blob(oid).ok()?;
+        let report = language.parse_from_slice(blob.content(), tokei_config).ok()?;
+        Some(BlobStats {
+            language,
+            code: report.stats.code,
+            comments: report.stats.comments,
+            blanks: report.stats.blanks,
+        })
+    })();
+    cache.put(key, stats.clone());
+    stats
+}
+
+/// Aggregates per-language `CodeStats` for a commit's tree by walking git
+/// objects directly (no working-directory checkout) and reusing cached
+/// per-blob results wherever possible.
+fn tree_stats(
+    repository: &Repository,
+    tree: &git2::Tree,
+    cache: &mut LruCache<(Oid, LanguageType), Option<BlobStats>>,
+    tokei_config: &tokei::Config,
+) -> Vec<CodeStats> {
+    let mut by_language: HashMap<LanguageType, (usize, usize, usize, usize)> = HashMap::new();
+    let _ = tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        if entry.kind() != Some(ObjectType::Blob) {
+            return TreeWalkResult::Ok;
+        }
+        // Symlinks are `ObjectType::Blob` too, but their "content" is just the
+        // link target path, not file contents - skip them so tokei doesn't
+        // count a bogus file per symlink.
+        if entry.filemode() == i32::from(git2::FileMode::Link) {
+            return TreeWalkResult::Ok;
+        }
+        let Some(name) = entry.name() else {
+            return TreeWalkResult::Ok;
+        };
+        let path = Path::new(root).join(name);
+        if let Some(stats) = stats_for_blob(repository, entry.id(), &path, cache, tokei_config) {
+            let totals = by_language.entry(stats.language).or_insert((0, 0, 0, 0));
+            totals.0 += 1;
+            totals.1 += stats.code;
+            totals.2 += stats.comments;
+            totals.3 += stats.blanks;
+        }
+        TreeWalkResult::Ok
+    });
+    by_language
+        .into_iter()
+        .map(|(language, (files, code, comments, blanks))| CodeStats {
+            language,
+            files,
+            code,
+            comments,
+            blanks,
+        })
+        .collect()
+}
+
 fn is_local_repo(url: &str) -> bool {
     Path::new(url).exists()
 }
 
-fn clone_remote(repo_url: &str, target: &Path) -> Result<Repository, LoaderError> {
+fn clone_remote(
+    repo_url: &str,
+    target: &Path,
+    data_tx: &SyncSender<Result<LoaderData, LoaderError>>,
+) -> Result<Repository, LoaderError> {
     let mut callbacks = RemoteCallbacks::new();
     callbacks.credentials(|_url, username_from_url, allowed_types| {
         if allowed_types.is_ssh_key() {
@@ -217,6 +335,14 @@ fn clone_remote(repo_url: &str, target: &Path) -> Result<Repository, LoaderError
             Err(git2::Error::from_str("Unsupported credential type"))
         }
     });
+    callbacks.transfer_progress(|progress| {
+        let _ = data_tx.send(Ok(LoaderData::FetchProgress {
+            received_objects: progress.received_objects(),
+            total_objects: progress.total_objects(),
+            received_bytes: progress.received_bytes(),
+        }));
+        true
+    });
 
     let mut fetch_opts = FetchOptions::new();
     fetch_opts.remote_callbacks(callbacks);
@@ -1,7 +1,12 @@
 use clap::Parser;
 
-use crate::app::Config;
+use crate::core::Config;
 mod app;
+mod conventional_commit;
+mod core;
+mod loader;
+mod tui;
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about=None)]
 struct CliArgs {
@@ -14,6 +19,9 @@ struct CliArgs {
     /// Branch name
     #[arg(short, long)]
     branch: Option<String>,
+    /// Run the terminal (headless) frontend instead of opening a window
+    #[arg(long)]
+    tui: bool,
 }
 fn main() -> eframe::Result {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
@@ -24,6 +32,12 @@ fn main() -> eframe::Result {
         repo_branch: args.branch.unwrap_or_default(),
     };
     log::debug!("Parsed config: {config:?}");
+    if args.tui {
+        if let Err(e) = tui::run(config) {
+            log::error!("TUI frontend failed: {e}");
+        }
+        return Ok(());
+    }
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1920.0, 1080.])
@@ -0,0 +1,170 @@
+use std::io;
+use std::panic;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::symbols;
+use ratatui::widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, List, ListItem, Paragraph};
+use ratatui::{Frame, Terminal};
+
+use crate::core::{Config, Core, LoaderState};
+use crate::loader::CodeStats;
+
+/// Metrics a user can pan between with the left/right arrow keys.
+const METRICS: [(&str, fn(&CodeStats) -> usize); 2] = [("code", |s| s.code), ("files", |s| s.files)];
+
+/// Restores the terminal before handing off to the default panic hook, so a
+/// panic mid-run (e.g. the loader thread dying) doesn't leave an SSH user's
+/// terminal stuck in raw/alt-screen mode with the panic message mangled into
+/// the app's last frame.
+fn install_panic_hook() {
+    let original_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        original_hook(panic_info);
+    }));
+}
+
+/// Runs the ratatui frontend over the same `Core` pipeline the egui app
+/// uses, so both render identical `CommitReport` data — just in a terminal
+/// instead of a window. Useful on servers and over SSH where there's no
+/// display to open an `eframe` window on.
+pub fn run(config: Config) -> io::Result<()> {
+    install_panic_hook();
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let result = run_app(&mut terminal, config);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
+
+fn run_app<B: Backend>(terminal: &mut Terminal<B>, config: Config) -> io::Result<()> {
+    let mut core = Core::new(config);
+    core.start();
+    let mut metric_index = 0usize;
+
+    loop {
+        core.poll();
+        terminal.draw(|frame| draw(frame, &core, metric_index))?;
+
+        if event::poll(Duration::from_millis(200))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => break,
+                    KeyCode::Char('r') => core.start(),
+                    KeyCode::Left => {
+                        metric_index = (metric_index + METRICS.len() - 1) % METRICS.len();
+                    }
+                    KeyCode::Right => {
+                        metric_index = (metric_index + 1) % METRICS.len();
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn draw(frame: &mut Frame, core: &Core, metric_index: usize) {
+    let (metric_name, getter) = METRICS[metric_index];
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(frame.area());
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(80), Constraint::Percentage(20)])
+        .split(rows[0]);
+
+    let data = core.collect_data(getter);
+    let point_sets: Vec<Vec<(f64, f64)>> = data
+        .iter()
+        .map(|(_, points)| points.iter().map(|p| (p.date, p.value)).collect())
+        .collect();
+
+    let mut min_x = f64::MAX;
+    let mut max_x = f64::MIN;
+    let mut max_y: f64 = 0.0;
+    for points in &point_sets {
+        for (x, y) in points {
+            min_x = min_x.min(*x);
+            max_x = max_x.max(*x);
+            max_y = max_y.max(*y);
+        }
+    }
+    if min_x > max_x {
+        min_x = 0.0;
+        max_x = 1.0;
+    }
+
+    let datasets: Vec<Dataset> = data
+        .iter()
+        .zip(point_sets.iter())
+        .map(|((language, _), points)| {
+            let color = core
+                .language_colors
+                .get(language)
+                .map(|[r, g, b]| Color::Rgb(*r, *g, *b))
+                .unwrap_or(Color::White);
+            Dataset::default()
+                .name(language.to_string())
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(color))
+                .data(points)
+        })
+        .collect();
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("LOC over time ({metric_name})")),
+        )
+        .x_axis(Axis::default().title("Date").bounds([min_x, max_x]))
+        .y_axis(Axis::default().title(metric_name).bounds([0.0, max_y.max(1.0)]));
+    frame.render_widget(chart, cols[0]);
+
+    let legend_items: Vec<ListItem> = data
+        .iter()
+        .map(|(language, _)| {
+            let [r, g, b] = *core.language_colors.get(language).unwrap();
+            ListItem::new(language.to_string()).style(Style::default().fg(Color::Rgb(r, g, b)))
+        })
+        .collect();
+    let legend = List::new(legend_items).block(Block::default().borders(Borders::ALL).title("Languages"));
+    frame.render_widget(legend, cols[1]);
+
+    let status = match &core.loader_state {
+        LoaderState::Idle => "idle".to_owned(),
+        LoaderState::Cloning {
+            received_objects,
+            total_objects,
+            ..
+        } => format!("cloning: {received_objects}/{total_objects} objects"),
+        LoaderState::Walking { current, total } => format!("analyzing commit {current} of {total}"),
+        LoaderState::Done => "done".to_owned(),
+        LoaderState::Error(e) => format!("error: {e}"),
+    };
+    let status = Paragraph::new(format!(
+        "{status}  |  q: quit  r: re-run  <-/->: pan metric ({metric_name})"
+    ));
+    frame.render_widget(status, rows[1]);
+}